@@ -0,0 +1,258 @@
+use std::env;
+use std::process::Command;
+
+/// Abstracts over "what window has focus, and what is it" so the rest of the
+/// daemon doesn't need to know whether we're on X11, a Wayland compositor, or
+/// macOS. Mirrors the shape Zed itself uses to stay cross-platform: a small
+/// trait with concrete per-backend implementations, picked once at startup.
+pub trait ActivitySource: Send + Sync {
+    /// PID of the process owning the currently focused window, if any.
+    fn active_window_pid(&self) -> Option<u32>;
+
+    /// Raw title of the currently focused window, if any. Backends don't
+    /// attempt to extract a filename from it — for editors like Zed that
+    /// pack the filename into a larger composite title, callers should run
+    /// this through `extract_filename_from_title`.
+    fn active_window_title(&self) -> Option<String>;
+
+    /// Whether the focused window belongs to one of `editor_names`.
+    fn is_editor_focused(&self, editor_names: &[String]) -> bool;
+}
+
+fn get_process_name(pid: u32) -> Option<String> {
+    let cmdline_path = format!("/proc/{}/cmdline", pid);
+    let cmdline = std::fs::read_to_string(cmdline_path).ok()?;
+    cmdline.split('\0').next()?.split('/').last().map(String::from)
+}
+
+fn matches_editor(name: &str, editor_names: &[String]) -> bool {
+    let name = name.to_lowercase();
+    editor_names.iter().any(|editor| name.contains(&editor.to_lowercase()))
+}
+
+/// Window titles for editors like Zed are composite strings — `"my-project
+/// — main.rs"` — with the filename as the last segment. Every
+/// backend hands back the raw title, so callers run it through this to pull
+/// the filename segment back out, regardless of which backend produced it.
+pub fn extract_filename_from_title(title: &str) -> Option<String> {
+    let filename = if title.contains(" — ") {
+        title.split(" — ").last()
+    } else {
+        title.split(" - ").next()
+    };
+
+    let filename = filename?.trim();
+    if filename.is_empty() || filename == "Zed" {
+        None
+    } else {
+        Some(filename.to_string())
+    }
+}
+
+/// X11 backend: current behavior, implemented on top of `xdotool`.
+pub struct X11Source;
+
+impl ActivitySource for X11Source {
+    fn active_window_pid(&self) -> Option<u32> {
+        let output = Command::new("xdotool")
+            .args(["getactivewindow", "getwindowpid"])
+            .output()
+            .ok()?;
+
+        if output.status.success() {
+            String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+        } else {
+            None
+        }
+    }
+
+    fn active_window_title(&self) -> Option<String> {
+        let output = Command::new("xdotool")
+            .args(["getactivewindow", "getwindowname"])
+            .output()
+            .ok()?;
+
+        if output.status.success() {
+            let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !title.is_empty() {
+                return Some(title);
+            }
+        }
+        None
+    }
+
+    fn is_editor_focused(&self, editor_names: &[String]) -> bool {
+        if let Some(pid) = self.active_window_pid() {
+            if let Some(name) = get_process_name(pid) {
+                return matches_editor(&name, editor_names);
+            }
+        }
+        false
+    }
+}
+
+/// Wayland backend: queries the compositor's focused-node tree instead of
+/// relying on X11-only tooling. Tries sway/wlroots first via `swaymsg`, then
+/// falls back to `hyprctl` for Hyprland.
+pub struct WaylandSource;
+
+impl WaylandSource {
+    fn sway_focused_node(&self) -> Option<serde_json::Value> {
+        let output = Command::new("swaymsg")
+            .args(["-t", "get_tree"])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let tree: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        find_focused_node(&tree)
+    }
+
+    fn hyprctl_active_window(&self) -> Option<serde_json::Value> {
+        let output = Command::new("hyprctl")
+            .args(["activewindow", "-j"])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        serde_json::from_slice(&output.stdout).ok()
+    }
+}
+
+fn find_focused_node(node: &serde_json::Value) -> Option<serde_json::Value> {
+    if node.get("focused").and_then(|v| v.as_bool()) == Some(true) {
+        return Some(node.clone());
+    }
+    for child in node.get("nodes").and_then(|v| v.as_array()).into_iter().flatten() {
+        if let Some(found) = find_focused_node(child) {
+            return Some(found);
+        }
+    }
+    for child in node
+        .get("floating_nodes")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(found) = find_focused_node(child) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+impl ActivitySource for WaylandSource {
+    fn active_window_pid(&self) -> Option<u32> {
+        if let Some(node) = self.sway_focused_node() {
+            if let Some(pid) = node.get("pid").and_then(|v| v.as_u64()) {
+                return Some(pid as u32);
+            }
+        }
+        if let Some(win) = self.hyprctl_active_window() {
+            if let Some(pid) = win.get("pid").and_then(|v| v.as_u64()) {
+                return Some(pid as u32);
+            }
+        }
+        None
+    }
+
+    fn active_window_title(&self) -> Option<String> {
+        if let Some(node) = self.sway_focused_node() {
+            if let Some(name) = node.get("name").and_then(|v| v.as_str()) {
+                if !name.is_empty() {
+                    return Some(name.to_string());
+                }
+            }
+        }
+        if let Some(win) = self.hyprctl_active_window() {
+            if let Some(title) = win.get("title").and_then(|v| v.as_str()) {
+                if !title.is_empty() {
+                    return Some(title.to_string());
+                }
+            }
+        }
+        None
+    }
+
+    fn is_editor_focused(&self, editor_names: &[String]) -> bool {
+        if let Some(pid) = self.active_window_pid() {
+            if let Some(name) = get_process_name(pid) {
+                return matches_editor(&name, editor_names);
+            }
+        }
+        false
+    }
+}
+
+/// macOS backend: asks the frontmost app and its window title via
+/// `osascript`, since there's no xdotool/compositor-IPC equivalent there.
+pub struct MacosSource;
+
+impl MacosSource {
+    fn frontmost_app_name(&self) -> Option<String> {
+        let script = r#"tell application "System Events" to get name of first process whose frontmost is true"#;
+        let output = Command::new("osascript").args(["-e", script]).output().ok()?;
+
+        if output.status.success() {
+            let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !name.is_empty() {
+                return Some(name);
+            }
+        }
+        None
+    }
+}
+
+impl ActivitySource for MacosSource {
+    fn active_window_pid(&self) -> Option<u32> {
+        let script = r#"tell application "System Events" to get unix id of first process whose frontmost is true"#;
+        let output = Command::new("osascript").args(["-e", script]).output().ok()?;
+
+        if output.status.success() {
+            String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+        } else {
+            None
+        }
+    }
+
+    fn active_window_title(&self) -> Option<String> {
+        let script = r#"tell application "System Events" to get name of front window of (first process whose frontmost is true)"#;
+        let output = Command::new("osascript").args(["-e", script]).output().ok()?;
+
+        if output.status.success() {
+            let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !title.is_empty() {
+                return Some(title);
+            }
+        }
+        None
+    }
+
+    fn is_editor_focused(&self, editor_names: &[String]) -> bool {
+        self.frontmost_app_name()
+            .map(|name| matches_editor(&name, editor_names))
+            .unwrap_or(false)
+    }
+}
+
+/// Detects the current session type from the environment and picks the
+/// matching backend, instead of hard-failing when `xdotool` is missing.
+pub fn detect_activity_source() -> Box<dyn ActivitySource> {
+    if cfg!(target_os = "macos") {
+        return Box::new(MacosSource);
+    }
+
+    if env::var("WAYLAND_DISPLAY").is_ok()
+        || env::var("XDG_SESSION_TYPE").map(|v| v == "wayland").unwrap_or(false)
+    {
+        return Box::new(WaylandSource);
+    }
+
+    Box::new(X11Source)
+}
@@ -0,0 +1,82 @@
+use clap::Subcommand;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+use crate::control::{socket_path, ControlRequest, ControlResponse};
+use crate::get_display_name;
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print the current file/language and today's accumulated per-language totals
+    Status,
+    /// Pause recording without stopping the daemon
+    Pause,
+    /// Resume recording
+    Resume,
+    /// Force an immediate send of pending activity
+    Flush,
+    /// Re-read config.toml
+    Reload,
+}
+
+/// Connects to the running daemon's control socket, sends one command, and
+/// prints the reply. Exits non-zero if the daemon is unreachable or replies
+/// with an error.
+pub async fn run(command: Command) {
+    let request = match command {
+        Command::Status => ControlRequest::Status,
+        Command::Pause => ControlRequest::Pause,
+        Command::Resume => ControlRequest::Resume,
+        Command::Flush => ControlRequest::Flush,
+        Command::Reload => ControlRequest::Reload,
+    };
+
+    match send_request(&request).await {
+        Ok(response) => print_response(response),
+        Err(e) => {
+            eprintln!("✗ Could not reach coredump daemon: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn send_request(request: &ControlRequest) -> std::io::Result<ControlResponse> {
+    let mut stream = UnixStream::connect(socket_path()).await?;
+
+    let mut payload = serde_json::to_vec(request)?;
+    payload.push(b'\n');
+    stream.write_all(&payload).await?;
+
+    let (reader, _) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let line = lines.next_line().await?.ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "daemon closed the connection")
+    })?;
+
+    serde_json::from_str(&line).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn print_response(response: ControlResponse) {
+    match response {
+        ControlResponse::Status(status) => {
+            match (&status.current_file, &status.current_language) {
+                (Some(file), Some(language)) => {
+                    println!("→ {} [{}]", file, get_display_name(language));
+                }
+                _ => println!("→ (idle)"),
+            }
+            println!("{}", if status.paused { "paused" } else { "recording" });
+
+            let mut totals: Vec<_> = status.daily_totals_minutes.into_iter().collect();
+            totals.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            for (language, minutes) in totals {
+                println!("  {:<12} {:.2}m", get_display_name(&language), minutes);
+            }
+        }
+        ControlResponse::Ok => println!("✓ ok"),
+        ControlResponse::Error { message } => {
+            eprintln!("✗ {}", message);
+            std::process::exit(1);
+        }
+    }
+}
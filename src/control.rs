@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+use crate::{build_http_client, get_display_name, load_config, ActivityTracker, Config};
+use crate::queue::{self, QueuedRecord};
+
+/// Request understood by the control socket. Mirrors the `coredump`
+/// subcommands one-to-one.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlRequest {
+    Status,
+    Pause,
+    Resume,
+    Flush,
+    Reload,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusReply {
+    pub current_file: Option<String>,
+    pub current_language: Option<String>,
+    pub paused: bool,
+    pub daily_totals_minutes: HashMap<String, f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum ControlResponse {
+    Status(StatusReply),
+    Ok,
+    Error { message: String },
+}
+
+/// Live state the control socket reads and mutates, shared with the main
+/// tracking loop.
+#[derive(Clone)]
+pub struct ControlState {
+    pub tracker: Arc<Mutex<ActivityTracker>>,
+    pub config: Arc<Mutex<Config>>,
+    pub client: Arc<Mutex<reqwest::Client>>,
+}
+
+pub fn socket_path() -> PathBuf {
+    crate::runtime_dir().join("coredump.sock")
+}
+
+/// Binds the control socket and serves requests until the process exits.
+/// Each connection is handled on its own task so a slow or misbehaving CLI
+/// invocation can't block the others.
+pub async fn serve(state: ControlState) -> std::io::Result<()> {
+    let path = socket_path();
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, state).await {
+                eprintln!("✗ Control connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: UnixStream, state: ControlState) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        let response = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => handle_request(request, &state).await,
+            Err(e) => ControlResponse::Error {
+                message: format!("invalid request: {}", e),
+            },
+        };
+
+        let mut payload = serde_json::to_vec(&response).unwrap_or_default();
+        payload.push(b'\n');
+        writer.write_all(&payload).await?;
+    }
+
+    Ok(())
+}
+
+async fn handle_request(request: ControlRequest, state: &ControlState) -> ControlResponse {
+    match request {
+        ControlRequest::Status => {
+            let tracker = state.tracker.lock().unwrap();
+            ControlResponse::Status(StatusReply {
+                current_file: tracker.current_file().cloned(),
+                current_language: tracker.current_language().cloned(),
+                paused: tracker.is_paused(),
+                daily_totals_minutes: tracker
+                    .daily_totals()
+                    .iter()
+                    .map(|(language, duration)| (language.clone(), duration.as_secs_f64() / 60.0))
+                    .collect(),
+            })
+        }
+        ControlRequest::Pause => {
+            state.tracker.lock().unwrap().pause();
+            ControlResponse::Ok
+        }
+        ControlRequest::Resume => {
+            state.tracker.lock().unwrap().resume();
+            ControlResponse::Ok
+        }
+        ControlRequest::Flush => {
+            flush_pending(state).await;
+            ControlResponse::Ok
+        }
+        ControlRequest::Reload => match load_config() {
+            Ok(new_config) => {
+                let new_client = build_http_client(&new_config);
+                state
+                    .tracker
+                    .lock()
+                    .unwrap()
+                    .apply_config(new_config.idle_threshold(), new_config.send_interval());
+                *state.client.lock().unwrap() = new_client;
+                *state.config.lock().unwrap() = new_config;
+                ControlResponse::Ok
+            }
+            Err(e) => ControlResponse::Error {
+                message: format!("failed to reload config: {}", e),
+            },
+        },
+    }
+}
+
+/// Spools and dispatches whatever is currently tracked, bypassing the usual
+/// send interval and minimum-duration filter — a `flush` means "send it now".
+async fn flush_pending(state: &ControlState) {
+    let data = state.tracker.lock().unwrap().get_and_reset();
+    let config = state.config.lock().unwrap().clone();
+    let client = state.client.lock().unwrap().clone();
+
+    for (language, duration) in data {
+        let minutes = duration.as_secs_f64() / 60.0;
+        let display = get_display_name(&language);
+        let record = QueuedRecord::now(language, minutes);
+
+        match queue::spool(&record) {
+            Ok(path) => {
+                tokio::spawn(queue::flush_record(client.clone(), config.clone(), path, record));
+            }
+            Err(e) => eprintln!("✗ Failed to spool {} for flush: {}", display, e),
+        }
+    }
+}
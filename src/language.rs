@@ -0,0 +1,149 @@
+/// One canonical language: its id (used in the stats we send), its display
+/// name, and the extensions that map to it. `detect_language` and
+/// `get_display_name` are both driven from this single table so they can't
+/// drift out of sync with each other.
+struct LanguageEntry {
+    id: &'static str,
+    display: &'static str,
+    extensions: &'static [&'static str],
+}
+
+const LANGUAGES: &[LanguageEntry] = &[
+    LanguageEntry { id: "rust", display: "Rust", extensions: &["rs"] },
+    LanguageEntry { id: "javascript", display: "JS", extensions: &["js", "mjs", "cjs"] },
+    LanguageEntry { id: "typescript", display: "TS", extensions: &["ts"] },
+    LanguageEntry { id: "typescriptreact", display: "TSX", extensions: &["tsx"] },
+    LanguageEntry { id: "javascriptreact", display: "JSX", extensions: &["jsx"] },
+    LanguageEntry { id: "python", display: "Python", extensions: &["py"] },
+    LanguageEntry { id: "go", display: "Go", extensions: &["go"] },
+    LanguageEntry { id: "java", display: "Java", extensions: &["java"] },
+    LanguageEntry { id: "cpp", display: "C++", extensions: &["cpp", "cc", "cxx", "h", "hpp"] },
+    LanguageEntry { id: "c", display: "C", extensions: &["c"] },
+    LanguageEntry { id: "csharp", display: "C#", extensions: &["cs"] },
+    LanguageEntry { id: "ruby", display: "Ruby", extensions: &["rb"] },
+    LanguageEntry { id: "php", display: "PHP", extensions: &["php"] },
+    LanguageEntry { id: "swift", display: "Swift", extensions: &["swift"] },
+    LanguageEntry { id: "kotlin", display: "Kotlin", extensions: &["kt", "kts"] },
+    LanguageEntry { id: "scala", display: "Scala", extensions: &["scala"] },
+    LanguageEntry { id: "bash", display: "Bash", extensions: &["sh", "bash"] },
+    LanguageEntry { id: "html", display: "HTML", extensions: &["html"] },
+    LanguageEntry { id: "css", display: "CSS", extensions: &["css"] },
+    LanguageEntry { id: "scss", display: "SCSS", extensions: &["scss", "sass"] },
+    LanguageEntry { id: "json", display: "JSON", extensions: &["json"] },
+    LanguageEntry { id: "yaml", display: "YAML", extensions: &["yaml", "yml"] },
+    LanguageEntry { id: "toml", display: "TOML", extensions: &["toml"] },
+    LanguageEntry { id: "xml", display: "XML", extensions: &["xml"] },
+    LanguageEntry { id: "markdown", display: "MD", extensions: &["md"] },
+    LanguageEntry { id: "sql", display: "SQL", extensions: &["sql"] },
+    LanguageEntry { id: "vim", display: "Vimscript", extensions: &["vim"] },
+    LanguageEntry { id: "lua", display: "Lua", extensions: &["lua"] },
+    LanguageEntry { id: "r", display: "R", extensions: &["r"] },
+    LanguageEntry { id: "dart", display: "Dart", extensions: &["dart"] },
+    LanguageEntry { id: "elixir", display: "Elixir", extensions: &["ex", "exs"] },
+    LanguageEntry { id: "erlang", display: "Erlang", extensions: &["erl"] },
+    LanguageEntry { id: "clojure", display: "Clojure", extensions: &["clj", "cljs"] },
+    LanguageEntry { id: "haskell", display: "Haskell", extensions: &["hs"] },
+    LanguageEntry { id: "ocaml", display: "OCaml", extensions: &["ml", "mli"] },
+    LanguageEntry { id: "elm", display: "Elm", extensions: &["elm"] },
+    LanguageEntry { id: "vue", display: "Vue", extensions: &["vue"] },
+    LanguageEntry { id: "svelte", display: "Svelte", extensions: &["svelte"] },
+    LanguageEntry { id: "perl", display: "Perl", extensions: &["pl", "pm"] },
+    LanguageEntry { id: "dockerfile", display: "Dockerfile", extensions: &[] },
+    LanguageEntry { id: "makefile", display: "Makefile", extensions: &[] },
+    LanguageEntry { id: "cmake", display: "CMake", extensions: &["cmake"] },
+    LanguageEntry { id: "gitignore", display: "Git Ignore", extensions: &[] },
+    LanguageEntry { id: "plaintext", display: "Text", extensions: &["txt"] },
+];
+
+/// Exact (case-sensitive) filename matches, checked before the extension
+/// table so dotfiles and extension-less project files aren't swallowed by
+/// the plaintext fallback.
+const FILENAME_RULES: &[(&str, &str)] = &[
+    ("Dockerfile", "dockerfile"),
+    ("Makefile", "makefile"),
+    ("GNUmakefile", "makefile"),
+    ("CMakeLists.txt", "cmake"),
+    (".gitignore", "gitignore"),
+];
+
+fn find_entry(id: &str) -> Option<&'static LanguageEntry> {
+    LANGUAGES.iter().find(|entry| entry.id == id)
+}
+
+fn detect_by_filename(filename: &str) -> Option<&'static str> {
+    let basename = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
+    FILENAME_RULES
+        .iter()
+        .find(|(name, _)| *name == basename)
+        .map(|(_, id)| *id)
+}
+
+fn detect_by_extension(filename: &str) -> Option<&'static str> {
+    if !filename.contains('.') {
+        return None;
+    }
+
+    let extension = filename.rsplit('.').next()?.to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|entry| entry.extensions.contains(&extension.as_str()))
+        .map(|entry| entry.id)
+}
+
+/// Classifies a focused file by, in order: exact filename, then extension —
+/// falling back to `"plaintext"`.
+///
+/// There's no shebang-sniffing fallback here: `filename` is whatever the
+/// active `ActivitySource` backend pulled out of a window title, which is
+/// just a basename, never a path we could actually open and read.
+pub fn detect_language(filename: &str) -> String {
+    detect_by_filename(filename)
+        .or_else(|| detect_by_extension(filename))
+        .unwrap_or("plaintext")
+        .to_string()
+}
+
+pub fn get_display_name(id: &str) -> &'static str {
+    find_entry(id).map(|entry| entry.display).unwrap_or("Unknown")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_extensions_added_alongside_newer_languages() {
+        assert_eq!(detect_language("mix.exs"), "elixir");
+        assert_eq!(detect_language("core.clj"), "clojure");
+        assert_eq!(detect_language("Cargo.toml"), "toml");
+        assert_eq!(detect_language("App.vue"), "vue");
+        assert_eq!(detect_language("App.svelte"), "svelte");
+    }
+
+    #[test]
+    fn filename_rules_take_precedence_over_extension() {
+        assert_eq!(detect_language("Dockerfile"), "dockerfile");
+        assert_eq!(detect_language(".gitignore"), "gitignore");
+        assert_eq!(detect_language("CMakeLists.txt"), "cmake");
+        assert_eq!(detect_language("/home/user/project/Makefile"), "makefile");
+    }
+
+    #[test]
+    fn falls_back_to_plaintext_for_unknown_files() {
+        assert_eq!(detect_language("notes.unknownext"), "plaintext");
+        assert_eq!(detect_language("README"), "plaintext");
+    }
+
+    #[test]
+    fn falls_back_to_plaintext_for_extensionless_scripts() {
+        // No shebang sniffing: `filename` is never a real path we could
+        // open, so an extension-less name always falls through to plaintext.
+        assert_eq!(detect_language("deploy"), "plaintext");
+    }
+
+    #[test]
+    fn get_display_name_falls_back_to_unknown() {
+        assert_eq!(get_display_name("rust"), "Rust");
+        assert_eq!(get_display_name("not-a-real-language"), "Unknown");
+    }
+}
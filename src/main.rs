@@ -1,55 +1,151 @@
+mod activity_source;
+mod cli;
+mod control;
+mod language;
+mod queue;
+
+use activity_source::{detect_activity_source, extract_filename_from_title, ActivitySource};
+use clap::Parser;
+use control::ControlState;
+pub(crate) use language::{detect_language, get_display_name};
+use queue::QueuedRecord;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::process::Command;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::time::interval;
 
-const API_URL: &str = "https://coredump.vercel.app/api/activity";
-const CHECK_INTERVAL: Duration = Duration::from_secs(5);
-const SEND_INTERVAL: Duration = Duration::from_secs(45);
-const MIN_SEND_DURATION: Duration = Duration::from_secs(30);
-const IDLE_THRESHOLD: Duration = Duration::from_secs(60);
+const DEFAULT_API_URL: &str = "https://coredump.vercel.app/api/activity";
+const DEFAULT_CHECK_INTERVAL_SECS: u64 = 5;
+const DEFAULT_SEND_INTERVAL_SECS: u64 = 45;
+const DEFAULT_MIN_SEND_SECS: u64 = 30;
+const DEFAULT_IDLE_THRESHOLD_SECS: u64 = 60;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_EDITORS: &[&str] = &["zed"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct Config {
+pub(crate) struct Config {
     private_key: String,
+    #[serde(default)]
+    api_url: Option<String>,
+    #[serde(default)]
+    check_interval_secs: Option<u64>,
+    #[serde(default)]
+    send_interval_secs: Option<u64>,
+    #[serde(default)]
+    min_send_secs: Option<u64>,
+    #[serde(default)]
+    idle_threshold_secs: Option<u64>,
+    #[serde(default)]
+    request_timeout_secs: Option<u64>,
+    #[serde(default)]
+    editors: Option<Vec<String>>,
+}
+
+impl Config {
+    pub(crate) fn api_url(&self) -> &str {
+        self.api_url.as_deref().unwrap_or(DEFAULT_API_URL)
+    }
+
+    pub(crate) fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval_secs.unwrap_or(DEFAULT_CHECK_INTERVAL_SECS))
+    }
+
+    pub(crate) fn send_interval(&self) -> Duration {
+        Duration::from_secs(self.send_interval_secs.unwrap_or(DEFAULT_SEND_INTERVAL_SECS))
+    }
+
+    fn min_send_duration(&self) -> Duration {
+        Duration::from_secs(self.min_send_secs.unwrap_or(DEFAULT_MIN_SEND_SECS))
+    }
+
+    pub(crate) fn idle_threshold(&self) -> Duration {
+        Duration::from_secs(self.idle_threshold_secs.unwrap_or(DEFAULT_IDLE_THRESHOLD_SECS))
+    }
+
+    fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs.unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS))
+    }
+
+    pub(crate) fn editors(&self) -> Vec<String> {
+        self.editors
+            .clone()
+            .unwrap_or_else(|| DEFAULT_EDITORS.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+/// Builds the shared HTTP client once at startup, honoring the configured
+/// request timeout and whichever TLS backend this binary was built with.
+/// Mirrors how other HTTP-client crates let callers pick `native-tls` vs
+/// `rustls-tls` at compile time.
+pub(crate) fn build_http_client(config: &Config) -> reqwest::Client {
+    let builder = reqwest::Client::builder().timeout(config.request_timeout());
+
+    #[cfg(feature = "native-tls")]
+    let builder = builder.use_native_tls();
+
+    #[cfg(feature = "rustls-tls")]
+    let builder = builder.use_rustls_tls();
+
+    builder.build().expect("failed to build HTTP client")
 }
 
 #[derive(Debug, Clone)]
-struct ActivityTracker {
+pub(crate) struct ActivityTracker {
     language_times: HashMap<String, Duration>,
+    daily_totals: HashMap<String, Duration>,
+    daily_epoch_day: u64,
     last_activity: Instant,
     last_sent: Instant,
     current_language: Option<String>,
     current_file: Option<String>,
+    paused: bool,
+    idle_threshold: Duration,
+    send_interval: Duration,
 }
 
 impl ActivityTracker {
-    fn new() -> Self {
+    fn new(idle_threshold: Duration, send_interval: Duration) -> Self {
         Self {
             language_times: HashMap::new(),
+            daily_totals: HashMap::new(),
+            daily_epoch_day: epoch_day(),
             last_activity: Instant::now(),
             last_sent: Instant::now(),
             current_language: None,
             current_file: None,
+            paused: false,
+            idle_threshold,
+            send_interval,
         }
     }
 
     fn record_activity(&mut self, language: String, filename: String) -> bool {
+        if self.paused {
+            return false;
+        }
+
+        let today = epoch_day();
+        if today != self.daily_epoch_day {
+            self.daily_epoch_day = today;
+            self.daily_totals.clear();
+        }
+
         let now = Instant::now();
         let time_since_last = now.duration_since(self.last_activity);
         let mut activity_changed = false;
 
-        if time_since_last < IDLE_THRESHOLD {
+        if time_since_last < self.idle_threshold {
             if let Some(lang) = &self.current_language {
-                let entry = self
+                *self
                     .language_times
                     .entry(lang.clone())
-                    .or_insert(Duration::ZERO);
-                *entry += time_since_last;
+                    .or_insert(Duration::ZERO) += time_since_last;
+                *self
+                    .daily_totals
+                    .entry(lang.clone())
+                    .or_insert(Duration::ZERO) += time_since_last;
             }
         }
 
@@ -66,8 +162,16 @@ impl ActivityTracker {
         activity_changed
     }
 
+    /// Applies freshly reloaded timing config. Doesn't touch anything else —
+    /// in-flight accumulated time, pause state, and the current file/language
+    /// are left alone.
+    pub(crate) fn apply_config(&mut self, idle_threshold: Duration, send_interval: Duration) {
+        self.idle_threshold = idle_threshold;
+        self.send_interval = send_interval;
+    }
+
     fn should_send(&self) -> bool {
-        Instant::now().duration_since(self.last_sent) >= SEND_INTERVAL
+        Instant::now().duration_since(self.last_sent) >= self.send_interval
     }
 
     fn get_and_reset(&mut self) -> HashMap<String, Duration> {
@@ -76,6 +180,41 @@ impl ActivityTracker {
         self.language_times.clear();
         data
     }
+
+    pub(crate) fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub(crate) fn resume(&mut self) {
+        self.paused = false;
+        self.last_activity = Instant::now();
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub(crate) fn current_file(&self) -> Option<&String> {
+        self.current_file.as_ref()
+    }
+
+    pub(crate) fn current_language(&self) -> Option<&String> {
+        self.current_language.as_ref()
+    }
+
+    pub(crate) fn daily_totals(&self) -> &HashMap<String, Duration> {
+        &self.daily_totals
+    }
+}
+
+/// Days since the Unix epoch, UTC. Used only to notice when "today" has
+/// rolled over so the daily totals reported by `coredump status` reset.
+fn epoch_day() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / 86_400
 }
 
 fn get_config_path() -> PathBuf {
@@ -83,6 +222,79 @@ fn get_config_path() -> PathBuf {
     home.join(".config/coredump/config.toml")
 }
 
+/// Where runtime-only state (the control socket, the PID file) lives.
+/// Falls back to `/tmp` when `XDG_RUNTIME_DIR` isn't set.
+pub(crate) fn runtime_dir() -> PathBuf {
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(runtime_dir)
+}
+
+fn pid_file_path() -> PathBuf {
+    runtime_dir().join("coredump.pid")
+}
+
+/// Whether `pid` names a process that's still running, checked by sending it
+/// the null signal rather than stat-ing `/proc/<pid>` — `/proc` doesn't exist
+/// on macOS, and this needs to work on every backend `detect_activity_source`
+/// supports. `kill` fails with `ESRCH` if the process is gone and `EPERM` if
+/// it's alive but owned by someone else, so `EPERM` still counts as alive.
+fn is_process_alive(pid: u32) -> bool {
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0 || std::io::Error::last_os_error().kind() == std::io::ErrorKind::PermissionDenied
+}
+
+/// Refuses to start a second instance: if a PID file exists and names a
+/// still-running process, another daemon owns it. A PID file left behind by
+/// a process that's no longer running is treated as stale and removed.
+fn check_single_instance() -> Result<(), Box<dyn std::error::Error>> {
+    let pid_path = pid_file_path();
+
+    if let Ok(content) = fs::read_to_string(&pid_path) {
+        if let Ok(pid) = content.trim().parse::<u32>() {
+            if is_process_alive(pid) {
+                return Err(format!("coredump is already running (pid {})", pid).into());
+            }
+        }
+        let _ = fs::remove_file(&pid_path);
+    }
+
+    Ok(())
+}
+
+/// Writes the current process's PID to the PID file. Used by the
+/// foreground path, where there's no fork to hand the file to us —
+/// `daemonize_process` writes its own via `Daemonize::pid_file`.
+fn write_pid_file() -> std::io::Result<()> {
+    fs::create_dir_all(runtime_dir())?;
+    fs::write(pid_file_path(), std::process::id().to_string())
+}
+
+/// Double-forks into the background, redirecting stdout/stderr to the log
+/// file and writing the PID file the single-instance guard checks.
+fn daemonize_process() -> Result<(), Box<dyn std::error::Error>> {
+    let log_dir = dirs::home_dir()
+        .expect("Could not find home directory")
+        .join(".config/coredump");
+    fs::create_dir_all(&log_dir)?;
+    let log_path = log_dir.join("coredump.log");
+    let stdout = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)?;
+    let stderr = stdout.try_clone()?;
+
+    let pid_path = pid_file_path();
+    fs::create_dir_all(runtime_dir())?;
+
+    daemonize::Daemonize::new()
+        .pid_file(&pid_path)
+        .stdout(stdout)
+        .stderr(stderr)
+        .start()?;
+
+    Ok(())
+}
+
 fn load_config() -> Result<Config, Box<dyn std::error::Error>> {
     let config_path = get_config_path();
 
@@ -99,190 +311,61 @@ fn load_config() -> Result<Config, Box<dyn std::error::Error>> {
     Ok(config)
 }
 
-fn get_active_window_pid() -> Option<u32> {
-    let output = Command::new("xdotool")
-        .args(["getactivewindow", "getwindowpid"])
-        .output()
-        .ok()?;
-
-    if output.status.success() {
-        let pid_str = String::from_utf8_lossy(&output.stdout);
-        pid_str.trim().parse().ok()
-    } else {
-        None
-    }
-}
+#[derive(Parser)]
+#[command(name = "coredump", about = "Tracks editor activity and reports it to CoreDump")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<cli::Command>,
 
-fn get_process_name(pid: u32) -> Option<String> {
-    let cmdline_path = format!("/proc/{}/cmdline", pid);
-    let cmdline = fs::read_to_string(cmdline_path).ok()?;
+    /// Fork into the background, detaching from the controlling terminal
+    #[arg(long, conflicts_with = "foreground")]
+    daemonize: bool,
 
-    let name = cmdline.split('\0').next()?.split('/').last()?.to_string();
-
-    Some(name)
+    /// Stay attached to the controlling terminal (default)
+    #[arg(long)]
+    foreground: bool,
 }
 
-fn is_zed_active() -> bool {
-    if let Some(pid) = get_active_window_pid() {
-        if let Some(name) = get_process_name(pid) {
-            return name.contains("zed") || name == "Zed";
-        }
-    }
-    false
-}
+fn main() {
+    let args = Cli::parse();
 
-fn get_current_file() -> Option<String> {
-    let output = Command::new("xdotool")
-        .args(["getactivewindow", "getwindowname"])
-        .output()
-        .ok()?;
-
-    if output.status.success() {
-        let title = String::from_utf8_lossy(&output.stdout);
-        let title = title.trim();
-
-        let filename = if title.contains(" — ") {
-            title.split(" — ").last()
-        } else {
-            title.split(" - ").next()
-        };
-
-        if let Some(filename) = filename {
-            let filename = filename.trim();
-            if !filename.is_empty() && filename != "Zed" {
-                return Some(filename.to_string());
-            }
-        }
-    }
-    None
-}
+    let should_daemonize = args.command.is_none() && args.daemonize && !args.foreground;
 
-fn detect_language(filename: &str) -> String {
-    let extension = filename.split('.').last().unwrap_or("");
-
-    match extension {
-        "rs" => "rust",
-        "js" => "javascript",
-        "ts" => "typescript",
-        "tsx" => "typescriptreact",
-        "jsx" => "javascriptreact",
-        "py" => "python",
-        "go" => "go",
-        "java" => "java",
-        "cpp" | "cc" | "cxx" => "cpp",
-        "c" => "c",
-        "h" | "hpp" => "cpp",
-        "cs" => "csharp",
-        "rb" => "ruby",
-        "php" => "php",
-        "swift" => "swift",
-        "kt" | "kts" => "kotlin",
-        "scala" => "scala",
-        "sh" | "bash" => "bash",
-        "html" => "html",
-        "css" => "css",
-        "scss" | "sass" => "scss",
-        "json" => "json",
-        "yaml" | "yml" => "yaml",
-        "toml" => "plaintext",
-        "xml" => "plaintext",
-        "md" => "markdown",
-        "sql" => "sql",
-        "vim" => "plaintext",
-        "lua" => "lua",
-        "r" => "r",
-        "dart" => "dart",
-        "ex" | "exs" => "plaintext",
-        "erl" => "plaintext",
-        "clj" | "cljs" => "plaintext",
-        "hs" => "haskell",
-        "ml" => "plaintext",
-        "elm" => "plaintext",
-        "vue" => "plaintext",
-        "svelte" => "plaintext",
-        _ => "plaintext",
+    if args.command.is_none() {
+        if let Err(e) = check_single_instance() {
+            eprintln!("✗ {}", e);
+            std::process::exit(1);
+        }
     }
-    .to_string()
-}
 
-fn get_display_name(lang: &str) -> &str {
-    match lang {
-        "rust" => "Rust",
-        "javascript" => "JS",
-        "typescript" => "TS",
-        "typescriptreact" => "TSX",
-        "javascriptreact" => "JSX",
-        "python" => "Python",
-        "go" => "Go",
-        "java" => "Java",
-        "cpp" => "C++",
-        "c" => "C",
-        "csharp" => "C#",
-        "ruby" => "Ruby",
-        "php" => "PHP",
-        "swift" => "Swift",
-        "kotlin" => "Kotlin",
-        "scala" => "Scala",
-        "bash" => "Bash",
-        "html" => "HTML",
-        "css" => "CSS",
-        "scss" => "SCSS",
-        "json" => "JSON",
-        "yaml" => "YAML",
-        "markdown" => "MD",
-        "sql" => "SQL",
-        "lua" => "Lua",
-        "r" => "R",
-        "dart" => "Dart",
-        "haskell" => "Haskell",
-        "plaintext" => "Text",
-        _ => "Unknown",
+    if should_daemonize {
+        // Must happen before the Tokio runtime is created: forking a
+        // process after its async worker threads exist is unsound, since
+        // the child only keeps the forking thread.
+        if let Err(e) = daemonize_process() {
+            eprintln!("✗ Failed to daemonize: {}", e);
+            std::process::exit(1);
+        }
+    } else if args.command.is_none() {
+        if let Err(e) = write_pid_file() {
+            eprintln!("✗ Failed to write PID file: {}", e);
+            std::process::exit(1);
+        }
     }
-}
 
-async fn send_activity(
-    config: &Config,
-    language: String,
-    minutes: f64,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let client = reqwest::Client::new();
-    let payload = serde_json::json!({
-        "privateKey": config.private_key,
-        "languageName": language,
-        "timeSpent": minutes
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start the Tokio runtime");
+    runtime.block_on(async {
+        match args.command {
+            Some(command) => cli::run(command).await,
+            None => run_daemon().await,
+        }
     });
-
-    let response = client
-        .post(API_URL)
-        .json(&payload)
-        .timeout(Duration::from_secs(10))
-        .send()
-        .await?;
-
-    let display = get_display_name(&language);
-    if response.status().is_success() {
-        println!("✓ Sent {:.2}m of {}", minutes, display);
-    } else {
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Could not read response".to_string());
-        eprintln!("✗ Failed to send: {} - {}", status, body);
-    }
-
-    Ok(())
 }
 
-#[tokio::main]
-async fn main() {
+async fn run_daemon() {
     println!("CoreDump - Starting...");
 
-    if Command::new("xdotool").arg("--version").output().is_err() {
-        eprintln!("Error: xdotool is required but not installed.");
-        eprintln!("Install it with: sudo apt-get install xdotool");
-        std::process::exit(1);
-    }
+    let source: Arc<dyn ActivitySource> = Arc::from(detect_activity_source());
 
     let config = match load_config() {
         Ok(c) => c,
@@ -291,68 +374,177 @@ async fn main() {
             std::process::exit(1);
         }
     };
+    let client = Arc::new(Mutex::new(build_http_client(&config)));
+    let config = Arc::new(Mutex::new(config));
 
     println!("✓ Config loaded");
     println!("✓ Monitoring...");
 
+    for (path, record) in queue::drain() {
+        let config = config.lock().unwrap().clone();
+        let client = client.lock().unwrap().clone();
+        tokio::spawn(queue::flush_record(client, config, path, record));
+    }
+
     tokio::time::sleep(Duration::from_millis(100)).await;
 
-    if is_zed_active() {
-        if let Some(filename) = get_current_file() {
-            let language = detect_language(&filename);
-            let display = get_display_name(&language);
-            println!("→ {} [{}]", filename, display);
+    let editors = config.lock().unwrap().editors();
+
+    if source.is_editor_focused(&editors) {
+        if let Some(title) = source.active_window_title() {
+            if let Some(filename) = extract_filename_from_title(&title) {
+                let language = detect_language(&filename);
+                let display = get_display_name(&language);
+                println!("→ {} [{}]", filename, display);
+            }
         }
     }
 
-    let tracker = Arc::new(Mutex::new(ActivityTracker::new()));
+    let (idle_threshold, send_interval) = {
+        let config = config.lock().unwrap();
+        (config.idle_threshold(), config.send_interval())
+    };
+    let tracker = Arc::new(Mutex::new(ActivityTracker::new(idle_threshold, send_interval)));
+
+    let control_state = ControlState {
+        tracker: tracker.clone(),
+        config: config.clone(),
+        client: client.clone(),
+    };
+    tokio::spawn(async move {
+        if let Err(e) = control::serve(control_state).await {
+            eprintln!("✗ Control socket error: {}", e);
+        }
+    });
+
     let tracker_clone = tracker.clone();
+    let source_clone = source.clone();
+    let config_clone = config.clone();
 
     tokio::spawn(async move {
-        let mut ticker = interval(CHECK_INTERVAL);
         let mut last_periodic_log = Instant::now();
 
         loop {
-            ticker.tick().await;
-
-            if is_zed_active() {
-                if let Some(filename) = get_current_file() {
-                    let language = detect_language(&filename);
-                    let mut tracker = tracker_clone.lock().unwrap();
-                    let activity_changed =
-                        tracker.record_activity(language.clone(), filename.clone());
-
-                    let display = get_display_name(&language);
-
-                    if activity_changed {
-                        println!("→ {} [{}]", filename, display);
-                        last_periodic_log = Instant::now();
-                    } else if last_periodic_log.elapsed() >= Duration::from_secs(300) {
-                        println!("→ {} [{}]", filename, display);
-                        last_periodic_log = Instant::now();
+            let check_interval = config_clone.lock().unwrap().check_interval();
+            tokio::time::sleep(check_interval).await;
+
+            let editors = config_clone.lock().unwrap().editors();
+            if source_clone.is_editor_focused(&editors) {
+                if let Some(title) = source_clone.active_window_title() {
+                    if let Some(filename) = extract_filename_from_title(&title) {
+                        let language = detect_language(&filename);
+                        let mut tracker = tracker_clone.lock().unwrap();
+                        let activity_changed =
+                            tracker.record_activity(language.clone(), filename.clone());
+
+                        let display = get_display_name(&language);
+
+                        if activity_changed {
+                            println!("→ {} [{}]", filename, display);
+                            last_periodic_log = Instant::now();
+                        } else if last_periodic_log.elapsed() >= Duration::from_secs(300) {
+                            println!("→ {} [{}]", filename, display);
+                            last_periodic_log = Instant::now();
+                        }
                     }
                 }
             }
         }
     });
 
-    let mut send_ticker = interval(SEND_INTERVAL);
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .expect("failed to install SIGTERM handler");
+
     loop {
-        send_ticker.tick().await;
-
-        let mut tracker = tracker.lock().unwrap();
-        if tracker.should_send() {
-            let data = tracker.get_and_reset();
-            drop(tracker);
-
-            for (language, duration) in data {
-                if duration >= MIN_SEND_DURATION {
-                    let minutes = duration.as_secs_f64() / 60.0;
-                    if let Err(e) = send_activity(&config, language, minutes).await {
-                        eprintln!("Error sending activity: {}", e);
+        let send_interval = config.lock().unwrap().send_interval();
+        tokio::select! {
+            _ = tokio::time::sleep(send_interval) => {
+                let mut tracker = tracker.lock().unwrap();
+                if tracker.should_send() {
+                    let data = tracker.get_and_reset();
+                    drop(tracker);
+
+                    let min_send_duration = config.lock().unwrap().min_send_duration();
+                    for (language, duration) in data {
+                        if duration >= min_send_duration {
+                            let minutes = duration.as_secs_f64() / 60.0;
+                            let record = QueuedRecord::now(language, minutes);
+
+                            match queue::spool(&record) {
+                                Ok(path) => {
+                                    let config = config.lock().unwrap().clone();
+                                    let client = client.lock().unwrap().clone();
+                                    tokio::spawn(queue::flush_record(client, config, path, record));
+                                }
+                                Err(e) => eprintln!("✗ Failed to spool activity record: {}", e),
+                            }
+                        }
                     }
                 }
             }
+            _ = sigterm.recv() => {
+                println!("Received SIGTERM, shutting down...");
+                flush_on_shutdown(&tracker, &config, &client).await;
+                break;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                println!("Received SIGINT, shutting down...");
+                flush_on_shutdown(&tracker, &config, &client).await;
+                break;
+            }
+        }
+    }
+
+    let _ = fs::remove_file(pid_file_path());
+}
+
+/// Flushes whatever is still pending in the tracker before the process
+/// exits, so a stop/restart doesn't discard the last partial interval. Each
+/// record is spooled (durable on disk regardless of what happens next) and
+/// given up to five seconds of send attempts; anything that doesn't land
+/// in time is picked up by the normal startup drain on the next run.
+async fn flush_on_shutdown(
+    tracker: &Arc<Mutex<ActivityTracker>>,
+    config: &Arc<Mutex<Config>>,
+    client: &Arc<Mutex<reqwest::Client>>,
+) {
+    let data = tracker.lock().unwrap().get_and_reset();
+    let config = config.lock().unwrap().clone();
+    let client = client.lock().unwrap().clone();
+
+    for (language, duration) in data {
+        let minutes = duration.as_secs_f64() / 60.0;
+        let record = QueuedRecord::now(language, minutes);
+
+        match queue::spool(&record) {
+            Ok(path) => {
+                let attempt = queue::flush_record(client.clone(), config.clone(), path, record);
+                if tokio::time::timeout(Duration::from_secs(5), attempt).await.is_err() {
+                    println!("✓ Queued pending activity for delivery on next start");
+                }
+            }
+            Err(e) => eprintln!("✗ Failed to spool activity record during shutdown: {}", e),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_process_alive_true_for_self() {
+        assert!(is_process_alive(std::process::id()));
+    }
+
+    #[test]
+    fn is_process_alive_false_once_reaped() {
+        let mut child = std::process::Command::new("true")
+            .spawn()
+            .expect("failed to spawn test child process");
+        let pid = child.id();
+        child.wait().expect("failed to wait on test child process");
+
+        assert!(!is_process_alive(pid));
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::Config;
+
+/// Cap on the exponential backoff between retries of a single record.
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// A single `(language, minutes, timestamp)` sample, durably spooled to disk
+/// before we ever attempt to send it, so a network error or a crash can't
+/// lose the minutes that were already tracked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedRecord {
+    pub language: String,
+    pub minutes: f64,
+    pub timestamp: u64,
+}
+
+impl QueuedRecord {
+    pub fn now(language: String, minutes: f64) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self {
+            language,
+            minutes,
+            timestamp,
+        }
+    }
+}
+
+fn queue_dir() -> PathBuf {
+    let home = dirs::home_dir().expect("Could not find home directory");
+    home.join(".config/coredump/queue")
+}
+
+/// Persists a record to the spool directory ahead of sending it. Each record
+/// gets its own file so a successful send can ack just that one entry
+/// without rewriting everything else still in flight.
+pub fn spool(record: &QueuedRecord) -> std::io::Result<PathBuf> {
+    let dir = queue_dir();
+    fs::create_dir_all(&dir)?;
+
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let path = dir.join(format!("{}-{}.json", record.timestamp, nonce));
+    fs::write(&path, serde_json::to_vec(record)?)?;
+    Ok(path)
+}
+
+/// Reads every record currently sitting in the spool, oldest first. Used on
+/// startup to pick up anything left over from a previous run that never made
+/// it out.
+pub fn drain() -> Vec<(PathBuf, QueuedRecord)> {
+    let dir = queue_dir();
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut records: Vec<(PathBuf, QueuedRecord)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let content = fs::read_to_string(&path).ok()?;
+            let record: QueuedRecord = serde_json::from_str(&content).ok()?;
+            Some((path, record))
+        })
+        .collect();
+
+    records.sort_by_key(|(_, record)| record.timestamp);
+    records
+}
+
+/// Removes a record from the spool once it has been accepted by the API.
+pub fn ack(path: &Path) {
+    if let Err(e) = fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            eprintln!("✗ Failed to remove queued record {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum SendOutcome {
+    Sent,
+    Permanent(String),
+    Retryable(String),
+}
+
+/// Turns a completed HTTP response into a `SendOutcome`: a 4xx is permanent
+/// (retrying it would never succeed), anything else non-2xx (such as a 5xx)
+/// is retryable. Network-level failures never get a status to classify;
+/// `try_send` marks those retryable itself.
+/// Split out from `try_send` so the classification can be unit-tested
+/// without a real HTTP round trip.
+fn classify_response(status: reqwest::StatusCode, body: String) -> SendOutcome {
+    if status.is_success() {
+        SendOutcome::Sent
+    } else if status.is_client_error() {
+        SendOutcome::Permanent(format!("{} - {}", status, body))
+    } else {
+        SendOutcome::Retryable(format!("{} - {}", status, body))
+    }
+}
+
+async fn try_send(client: &reqwest::Client, config: &Config, record: &QueuedRecord) -> SendOutcome {
+    let payload = serde_json::json!({
+        "privateKey": config.private_key,
+        "languageName": record.language,
+        "timeSpent": record.minutes
+    });
+
+    let response = match client.post(config.api_url()).json(&payload).send().await {
+        Ok(response) => response,
+        Err(e) => return SendOutcome::Retryable(e.to_string()),
+    };
+
+    let status = response.status();
+    if status.is_success() {
+        return SendOutcome::Sent;
+    }
+
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Could not read response".to_string());
+
+    classify_response(status, body)
+}
+
+/// Doubles `backoff`, capped at `MAX_BACKOFF`.
+fn next_backoff(backoff: Duration) -> Duration {
+    (backoff * 2).min(MAX_BACKOFF)
+}
+
+/// Drives a single queued record to completion: retries on retryable
+/// failures (5xx, timeouts, connection errors) with exponential backoff and
+/// jitter, capped at `MAX_BACKOFF`; drops the record immediately on a
+/// permanent (4xx) failure since retrying it would never succeed.
+pub async fn flush_record(client: reqwest::Client, config: Config, path: PathBuf, record: QueuedRecord) {
+    let mut backoff = Duration::from_secs(1);
+    let display = crate::get_display_name(&record.language);
+
+    loop {
+        match try_send(&client, &config, &record).await {
+            SendOutcome::Sent => {
+                println!("✓ Sent {:.2}m of {}", record.minutes, display);
+                ack(&path);
+                return;
+            }
+            SendOutcome::Permanent(msg) => {
+                eprintln!("✗ Dropping {:.2}m of {}: {}", record.minutes, display, msg);
+                ack(&path);
+                return;
+            }
+            SendOutcome::Retryable(msg) => {
+                eprintln!(
+                    "✗ Failed to send {:.2}m of {} (retrying in {:.1}s): {}",
+                    record.minutes,
+                    display,
+                    backoff.as_secs_f64(),
+                    msg
+                );
+                tokio::time::sleep(backoff + jitter(backoff)).await;
+                backoff = next_backoff(backoff);
+            }
+        }
+    }
+}
+
+/// A small random fraction of `backoff`, so many queued records don't all
+/// wake up and retry in the same instant.
+fn jitter(backoff: Duration) -> Duration {
+    use rand::Rng;
+    let fraction = rand::thread_rng().gen_range(0.0..0.25);
+    Duration::from_secs_f64(backoff.as_secs_f64() * fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_success_as_sent() {
+        assert_eq!(
+            classify_response(reqwest::StatusCode::OK, String::new()),
+            SendOutcome::Sent
+        );
+    }
+
+    #[test]
+    fn classifies_4xx_as_permanent() {
+        let outcome = classify_response(reqwest::StatusCode::BAD_REQUEST, "bad key".to_string());
+        assert!(matches!(outcome, SendOutcome::Permanent(msg) if msg.contains("bad key")));
+    }
+
+    #[test]
+    fn classifies_5xx_as_retryable() {
+        let outcome = classify_response(reqwest::StatusCode::INTERNAL_SERVER_ERROR, "oops".to_string());
+        assert!(matches!(outcome, SendOutcome::Retryable(msg) if msg.contains("oops")));
+    }
+
+    #[test]
+    fn backoff_doubles_each_retry() {
+        let first = Duration::from_secs(1);
+        let second = next_backoff(first);
+        let third = next_backoff(second);
+        assert_eq!(second, Duration::from_secs(2));
+        assert_eq!(third, Duration::from_secs(4));
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_backoff() {
+        let near_cap = Duration::from_secs(250);
+        assert_eq!(next_backoff(near_cap), MAX_BACKOFF);
+        assert_eq!(next_backoff(MAX_BACKOFF), MAX_BACKOFF);
+    }
+}